@@ -2,40 +2,133 @@
 
 use super::{Backend, Object};
 use async_trait::async_trait;
-use aws_config::ConfigLoader;
-use aws_sdk_s3::{Client, Endpoint};
+use aws_config::{
+    environment::credentials::EnvironmentVariableCredentialsProvider,
+    imds::credentials::ImdsCredentialsProvider, meta::credentials::CredentialsProviderChain,
+    profile::ProfileFileCredentialsProvider, retry::RetryConfig, ConfigLoader,
+};
+use aws_sdk_s3::{Client, Endpoint, Region};
 use bytes::Bytes;
 use eyre::{eyre, Context};
 use http::Uri;
 use lifterr::IntoOk;
 use log::warn;
 use serde::Deserialize;
+use std::time::Duration;
 
 #[derive(Debug, Deserialize)]
 pub struct Options {
     #[serde(rename = "endpoint", with = "opt_uri", default)]
     pub endpoint_uri: Option<Uri>,
+    /// Restricts the listing to keys under this prefix, i.e. mounts a sub-path of the bucket.
+    #[serde(rename = "prefix", default)]
+    pub prefix: Option<String>,
+    /// Overrides the region otherwise resolved from the environment.
+    #[serde(rename = "region", default)]
+    pub region: Option<String>,
+    /// Named profile consulted when credentials are resolved from the shared profile file.
+    #[serde(rename = "profile", default)]
+    pub profile: Option<String>,
+    /// Selects how credentials are resolved; defaults to the full chain.
+    #[serde(rename = "credentials", default)]
+    pub credentials: CredentialsSource,
+    /// Retry behaviour for retryable (throttling/5xx) errors.
+    #[serde(rename = "retry", default)]
+    pub retry: Option<RetryOptions>,
+}
+
+/// The source consulted to resolve credentials.
+#[derive(Debug, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialsSource {
+    /// `AWS_ACCESS_KEY_ID` / `AWS_SECRET_ACCESS_KEY` environment variables only.
+    Environment,
+    /// The shared profile file only.
+    Profile,
+    /// IMDS instance metadata only.
+    Imds,
+    /// Environment variables, then the shared profile file, then IMDS, in that order.
+    #[default]
+    Chain,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RetryOptions {
+    #[serde(rename = "max_attempts", default = "default_max_attempts")]
+    pub max_attempts: u32,
+    #[serde(rename = "initial_backoff_ms", default)]
+    pub initial_backoff_ms: Option<u64>,
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+impl From<RetryOptions> for RetryConfig {
+    fn from(opts: RetryOptions) -> Self {
+        // `with_max_attempts` panics on 0; treat it as "no retries" (a single attempt).
+        let mut config = RetryConfig::standard().with_max_attempts(opts.max_attempts.max(1));
+        if let Some(ms) = opts.initial_backoff_ms {
+            config = config.with_initial_backoff(Duration::from_millis(ms));
+        }
+        config
+    }
 }
 
 #[derive(Debug)]
 pub(in crate) struct Provider {
     inner: Client,
+    prefix: Option<String>,
 }
 
 impl Provider {
     pub async fn new(opts: Options) -> Self {
+        let prefix = opts.prefix.clone();
         let config = Self::new_config_with(opts).load().await;
         Self {
             inner: Client::new(&config),
+            prefix,
         }
     }
 
     fn new_config_with(opts: Options) -> ConfigLoader {
-        let config = aws_config::from_env();
+        let mut loader =
+            aws_config::from_env().credentials_provider(Self::credentials_chain(&opts));
+
+        if let Some(region) = opts.region {
+            loader = loader.region(Region::new(region));
+        }
+
+        if let Some(retry) = opts.retry {
+            loader = loader.retry_config(retry.into());
+        }
+
         if let Some(uri) = opts.endpoint_uri {
-            config.endpoint_resolver(Endpoint::immutable(uri))
-        } else {
-            config
+            loader = loader.endpoint_resolver(Endpoint::immutable(uri));
+        }
+
+        loader
+    }
+
+    fn credentials_chain(opts: &Options) -> CredentialsProviderChain {
+        let environment =
+            || CredentialsProviderChain::first_try("environment", EnvironmentVariableCredentialsProvider::new());
+        let profile = || {
+            let mut builder = ProfileFileCredentialsProvider::builder();
+            if let Some(name) = &opts.profile {
+                builder = builder.profile_name(name);
+            }
+            builder.build()
+        };
+        let imds = || ImdsCredentialsProvider::builder().build();
+
+        match opts.credentials {
+            CredentialsSource::Environment => environment(),
+            CredentialsSource::Profile => CredentialsProviderChain::first_try("profile", profile()),
+            CredentialsSource::Imds => CredentialsProviderChain::first_try("imds", imds()),
+            CredentialsSource::Chain => environment()
+                .or_else("profile", profile())
+                .or_else("imds", imds()),
         }
     }
 }
@@ -43,30 +136,83 @@ impl Provider {
 #[async_trait]
 impl Backend for Provider {
     async fn list_objects(&self, bucket_name: &str) -> eyre::Result<Vec<Object>> {
+        let mut objects = Vec::new();
+        let mut continuation_token = None;
+
+        // S3 caps each page at 1000 keys, so follow the continuation token until exhausted.
+        loop {
+            let page = self
+                .inner
+                .list_objects_v2()
+                .bucket(bucket_name)
+                .set_prefix(self.prefix.clone())
+                .set_continuation_token(continuation_token)
+                .send()
+                .await
+                .wrap_err_with(|| eyre!("unable to list objects in s3 bucket={}", bucket_name))?;
+
+            objects.extend(
+                page.contents
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(try_from_s3_object),
+            );
+
+            // A truncated page without a token would otherwise re-request the first page forever.
+            match page.next_continuation_token {
+                Some(token) if page.is_truncated => continuation_token = Some(token),
+                _ => break,
+            }
+        }
+
+        objects.into_ok()
+    }
+
+    async fn download_object(&self, bucket_name: &str, key: &str) -> eyre::Result<Bytes> {
         self.inner
-            .list_objects()
+            .get_object()
             .bucket(bucket_name)
+            .key(key)
             .send()
             .await
-            .wrap_err_with(|| eyre!("unable to list objects in s3 bucket={}", bucket_name))?
-            .contents
-            .unwrap_or_default()
-            .into_iter()
-            .filter_map(try_from_s3_object)
-            .collect::<Vec<_>>()
+            .wrap_err_with(|| {
+                eyre!(
+                    "unable to download object in s3 bucket={} with key={}",
+                    bucket_name,
+                    key
+                )
+            })?
+            .body
+            .collect()
+            .await
+            .wrap_err_with(|| {
+                eyre!(
+                    "unable to read full content of object in s3 bucket={} with key={}",
+                    bucket_name,
+                    key
+                )
+            })?
+            .into_bytes()
             .into_ok()
     }
 
-    async fn download_object(&self, bucket_name: &str, key: &str) -> eyre::Result<Bytes> {
+    async fn download_range(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Bytes> {
         self.inner
             .get_object()
             .bucket(bucket_name)
             .key(key)
+            .range(format!("bytes={}-{}", offset, offset + len - 1))
             .send()
             .await
             .wrap_err_with(|| {
                 eyre!(
-                    "unable to download object in s3 bucket={} with key={}",
+                    "unable to download range of object in s3 bucket={} with key={}",
                     bucket_name,
                     key
                 )
@@ -76,7 +222,7 @@ impl Backend for Provider {
             .await
             .wrap_err_with(|| {
                 eyre!(
-                    "unable to read full content of object in s3 bucket={} with key={}",
+                    "unable to read range content of object in s3 bucket={} with key={}",
                     bucket_name,
                     key
                 )
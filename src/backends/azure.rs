@@ -0,0 +1,109 @@
+//! A backend provided by an Azure Blob Storage container.
+
+use super::{Backend, Object};
+use async_trait::async_trait;
+use azure_core::request_options::Range;
+use azure_storage::StorageCredentials;
+use azure_storage_blobs::prelude::{ClientBuilder, ContainerClient};
+use bytes::{BufMut, Bytes, BytesMut};
+use eyre::{eyre, Context};
+use futures::StreamExt;
+use lifterr::IntoOk;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+pub struct Options {
+    #[serde(rename = "account")]
+    pub account: String,
+    #[serde(rename = "container")]
+    pub container: String,
+    /// Shared-key credential; when omitted the container is accessed anonymously.
+    #[serde(rename = "access_key", default)]
+    pub access_key: Option<String>,
+    /// Overrides the blob endpoint, e.g. to target the Azurite emulator.
+    #[serde(rename = "endpoint", default)]
+    pub endpoint: Option<String>,
+}
+
+#[derive(Debug)]
+pub(in crate) struct Provider {
+    container: ContainerClient,
+}
+
+impl Provider {
+    pub fn new(opts: Options) -> Self {
+        let credentials = match opts.access_key {
+            Some(key) => StorageCredentials::access_key(opts.account.clone(), key),
+            None => StorageCredentials::anonymous(),
+        };
+
+        let mut builder = ClientBuilder::new(opts.account, credentials);
+        if let Some(endpoint) = opts.endpoint {
+            builder = builder.blob_storage_url(endpoint);
+        }
+
+        Self {
+            container: builder.container_client(opts.container),
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for Provider {
+    async fn list_objects(&self, _bucket_name: &str) -> eyre::Result<Vec<Object>> {
+        let mut objects = Vec::new();
+        let mut pages = self.container.list_blobs().into_stream();
+
+        while let Some(page) = pages.next().await {
+            let page = page.wrap_err("unable to list blobs in azure container")?;
+            objects.extend(page.blobs.blobs().map(from_blob));
+        }
+
+        objects.into_ok()
+    }
+
+    async fn download_object(&self, _bucket_name: &str, key: &str) -> eyre::Result<Bytes> {
+        self.container
+            .blob_client(key)
+            .get_content()
+            .await
+            .wrap_err_with(|| eyre!("unable to download blob with key={}", key))
+            .map(Bytes::from)
+    }
+
+    async fn download_range(
+        &self,
+        _bucket_name: &str,
+        key: &str,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Bytes> {
+        let mut chunks = self
+            .container
+            .blob_client(key)
+            .get()
+            .range(Range::new(offset, offset + len))
+            .into_stream();
+
+        let mut buf = BytesMut::with_capacity(len as usize);
+        while let Some(chunk) = chunks.next().await {
+            let bytes = chunk
+                .wrap_err_with(|| eyre!("unable to download range of blob with key={}", key))?
+                .data
+                .collect()
+                .await
+                .wrap_err_with(|| eyre!("unable to read range of blob with key={}", key))?;
+            buf.put(bytes);
+        }
+
+        buf.freeze().into_ok()
+    }
+}
+
+fn from_blob(blob: &azure_storage_blobs::blob::Blob) -> Object {
+    Object {
+        name: blob.name.clone(),
+        size: blob.properties.content_length,
+        last_modified: blob.properties.last_modified.into(),
+    }
+}
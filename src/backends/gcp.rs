@@ -0,0 +1,123 @@
+//! A backend provided by a Google Cloud Storage bucket.
+
+use super::{Backend, Object};
+use async_trait::async_trait;
+use bytes::Bytes;
+use eyre::{eyre, Context};
+use google_cloud_storage::{
+    client::{Client, ClientConfig},
+    http::objects::{
+        download::Range, get::GetObjectRequest, list::ListObjectsRequest, Object as GcsObject,
+    },
+};
+use lifterr::IntoOk;
+use serde::Deserialize;
+use std::time::UNIX_EPOCH;
+
+#[derive(Debug, Deserialize)]
+pub struct Options {
+    #[serde(rename = "bucket")]
+    pub bucket: String,
+    /// Project hosting the bucket. Accepted in the configuration but not read by the provider:
+    /// objects are listed and downloaded by bucket name alone.
+    #[serde(rename = "project", default)]
+    pub project: Option<String>,
+    /// Overrides the storage endpoint, e.g. to target the fake-gcs-server emulator.
+    #[serde(rename = "endpoint", default)]
+    pub endpoint: Option<String>,
+}
+
+#[derive(Debug)]
+pub(in crate) struct Provider {
+    inner: Client,
+    bucket: String,
+}
+
+impl Provider {
+    pub async fn new(opts: Options) -> eyre::Result<Self> {
+        // Emulators (fake-gcs-server) are reached anonymously via an endpoint override; otherwise
+        // credentials resolve from the ambient environment.
+        let mut config = match opts.endpoint {
+            Some(_) => ClientConfig::default().anonymous(),
+            None => ClientConfig::default()
+                .with_auth()
+                .await
+                .wrap_err("unable to authenticate gcs client")?,
+        };
+        if let Some(endpoint) = opts.endpoint {
+            config.storage_endpoint = endpoint;
+        }
+
+        Self {
+            inner: Client::new(config),
+            bucket: opts.bucket,
+        }
+        .into_ok()
+    }
+
+    async fn get(&self, key: &str, range: Range) -> eyre::Result<Bytes> {
+        self.inner
+            .download_object(
+                &GetObjectRequest {
+                    bucket: self.bucket.clone(),
+                    object: key.to_owned(),
+                    ..Default::default()
+                },
+                &range,
+            )
+            .await
+            .wrap_err_with(|| eyre!("unable to download object with key={}", key))
+            .map(Bytes::from)
+    }
+}
+
+#[async_trait]
+impl Backend for Provider {
+    async fn list_objects(&self, _bucket_name: &str) -> eyre::Result<Vec<Object>> {
+        let mut objects = Vec::new();
+        let mut request = ListObjectsRequest {
+            bucket: self.bucket.clone(),
+            ..Default::default()
+        };
+
+        loop {
+            let response = self
+                .inner
+                .list_objects(&request)
+                .await
+                .wrap_err_with(|| eyre!("unable to list objects in gcs bucket={}", self.bucket))?;
+
+            objects.extend(response.items.unwrap_or_default().iter().map(from_gcs_object));
+
+            match response.next_page_token {
+                Some(token) => request.page_token = Some(token),
+                None => break,
+            }
+        }
+
+        objects.into_ok()
+    }
+
+    async fn download_object(&self, _bucket_name: &str, key: &str) -> eyre::Result<Bytes> {
+        self.get(key, Range(None, None)).await
+    }
+
+    async fn download_range(
+        &self,
+        _bucket_name: &str,
+        key: &str,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Bytes> {
+        // A genuine range GET: the half-open `[offset, offset + len)` maps to GCS' inclusive end.
+        self.get(key, Range(Some(offset), Some(offset + len - 1))).await
+    }
+}
+
+fn from_gcs_object(o: &GcsObject) -> Object {
+    Object {
+        name: o.name.clone(),
+        size: o.size as u64,
+        last_modified: o.updated.map(Into::into).unwrap_or(UNIX_EPOCH),
+    }
+}
@@ -0,0 +1,102 @@
+//! A backend backed by a directory on the local filesystem.
+
+use super::{Backend, Object};
+use async_trait::async_trait;
+use bytes::Bytes;
+use eyre::{eyre, Context};
+use lifterr::IntoOk;
+use serde::Deserialize;
+use std::{
+    fs,
+    io::{Read, Seek, SeekFrom},
+    path::{Path, PathBuf},
+};
+
+#[derive(Debug, Deserialize)]
+pub struct Options {
+    #[serde(rename = "root")]
+    pub root: PathBuf,
+}
+
+#[derive(Debug)]
+pub(in crate) struct Provider {
+    root: PathBuf,
+}
+
+impl Provider {
+    pub fn new(opts: Options) -> Self {
+        Self { root: opts.root }
+    }
+
+    fn walk(&self, dir: &Path, objects: &mut Vec<Object>) -> eyre::Result<()> {
+        let entries =
+            fs::read_dir(dir).wrap_err_with(|| eyre!("unable to read dir={}", dir.display()))?;
+        for entry in entries {
+            let path = entry.wrap_err("unable to read dir entry")?.path();
+            let meta = fs::metadata(&path)
+                .wrap_err_with(|| eyre!("unable to stat path={}", path.display()))?;
+            if meta.is_dir() {
+                self.walk(&path, objects)?;
+            } else if let Some(object) = self.try_object(&path, &meta) {
+                objects.push(object);
+            }
+        }
+        Ok(())
+    }
+
+    fn try_object(&self, path: &Path, meta: &fs::Metadata) -> Option<Object> {
+        // Keys mirror the object-store convention: root-relative and `/`-delimited.
+        let name = path
+            .strip_prefix(&self.root)
+            .ok()?
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        Object {
+            name,
+            size: meta.len(),
+            last_modified: meta.modified().ok()?,
+        }
+        .into_ok()
+    }
+}
+
+#[async_trait]
+impl Backend for Provider {
+    async fn list_objects(&self, _bucket_name: &str) -> eyre::Result<Vec<Object>> {
+        let mut objects = Vec::new();
+        self.walk(&self.root.clone(), &mut objects)
+            .wrap_err_with(|| eyre!("unable to list objects under root={}", self.root.display()))?;
+        objects.into_ok()
+    }
+
+    async fn download_object(&self, _bucket_name: &str, key: &str) -> eyre::Result<Bytes> {
+        let path = self.root.join(key);
+        fs::read(&path)
+            .wrap_err_with(|| eyre!("unable to read object at path={}", path.display()))
+            .map(Bytes::from)
+    }
+
+    async fn download_range(
+        &self,
+        _bucket_name: &str,
+        key: &str,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Bytes> {
+        let path = self.root.join(key);
+        let mut file = fs::File::open(&path)
+            .wrap_err_with(|| eyre!("unable to open object at path={}", path.display()))?;
+        file.seek(SeekFrom::Start(offset))
+            .wrap_err_with(|| eyre!("unable to seek object at path={}", path.display()))?;
+
+        let mut buf = Vec::with_capacity(len as usize);
+        file.take(len)
+            .read_to_end(&mut buf)
+            .wrap_err_with(|| eyre!("unable to read range of object at path={}", path.display()))?;
+
+        Bytes::from(buf).into_ok()
+    }
+}
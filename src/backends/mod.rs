@@ -1,25 +1,33 @@
 //! Backends for storage services.
 
 pub mod aws;
+pub mod azure;
+pub mod gcp;
+pub mod local;
 
 use async_trait::async_trait;
-use bytes::Bytes;
-use std::time::SystemTime;
+use bytes::{Bytes, BytesMut};
+use std::{collections::HashMap, sync::Mutex, time::SystemTime};
 use tokio::runtime::Runtime;
 
+/// Size of the fixed blocks that range reads are quantised into for caching.
+const BLOCK_SIZE: u64 = 1 << 20; // 1 MiB
+
 pub struct BlockingConnection {
     service: Box<dyn Backend>,
     rt: Runtime,
+    cache: Mutex<BlockCache>,
 }
 
 impl BlockingConnection {
-    pub(in crate) fn new<S>(service: S, rt: Runtime) -> Self
+    pub(in crate) fn new<S>(service: S, rt: Runtime, cache_max_bytes: u64) -> Self
     where
         S: 'static + Backend,
     {
         Self {
             service: Box::new(service),
             rt,
+            cache: Mutex::new(BlockCache::new(cache_max_bytes)),
         }
     }
 
@@ -31,6 +39,140 @@ impl BlockingConnection {
         self.rt
             .block_on(self.service.download_object(bucket_name, key))
     }
+
+    pub fn download_range(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Bytes> {
+        if len == 0 {
+            return Ok(Bytes::new());
+        }
+
+        // With no budget the cache is disabled: go straight to the backend.
+        if self.cache.lock().unwrap().max_bytes == 0 {
+            return self
+                .rt
+                .block_on(self.service.download_range(bucket_name, key, offset, len));
+        }
+
+        // Assemble the requested window out of the fixed-size blocks that cover it, fetching and
+        // caching any block that misses.
+        let end = offset + len;
+        let first = offset / BLOCK_SIZE;
+        let last = (end - 1) / BLOCK_SIZE;
+
+        let mut out = BytesMut::with_capacity(len as usize);
+        for index in first..=last {
+            let block_start = index * BLOCK_SIZE;
+            let block = self.block_at(bucket_name, key, index)?;
+
+            let from = (offset.max(block_start) - block_start) as usize;
+            let to = ((end.min(block_start + block.len() as u64)) - block_start) as usize;
+            if from < to.min(block.len()) {
+                out.extend_from_slice(&block[from..to.min(block.len())]);
+            }
+        }
+
+        Ok(out.freeze())
+    }
+
+    fn block_at(&self, bucket_name: &str, key: &str, index: u64) -> eyre::Result<Bytes> {
+        let cache_key = (bucket_name.to_owned(), key.to_owned(), index);
+
+        if let Some(block) = self.cache.lock().unwrap().get(&cache_key) {
+            return Ok(block);
+        }
+
+        let block = self.rt.block_on(self.service.download_range(
+            bucket_name,
+            key,
+            index * BLOCK_SIZE,
+            BLOCK_SIZE,
+        ))?;
+
+        self.cache.lock().unwrap().insert(cache_key, block.clone());
+
+        Ok(block)
+    }
+}
+
+/// A bounded, least-recently-used cache of object blocks, shared for the lifetime of the mount.
+struct BlockCache {
+    max_bytes: u64,
+    used_bytes: u64,
+    tick: u64,
+    blocks: HashMap<BlockKey, CachedBlock>,
+}
+
+/// `(bucket, key, block_index)`.
+type BlockKey = (String, String, u64);
+
+struct CachedBlock {
+    data: Bytes,
+    last_used: u64,
+}
+
+impl BlockCache {
+    fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            used_bytes: 0,
+            tick: 0,
+            blocks: HashMap::new(),
+        }
+    }
+
+    fn get(&mut self, key: &BlockKey) -> Option<Bytes> {
+        self.tick += 1;
+        let tick = self.tick;
+        let block = self.blocks.get_mut(key)?;
+        block.last_used = tick;
+        Some(block.data.clone())
+    }
+
+    fn insert(&mut self, key: BlockKey, data: Bytes) {
+        let len = data.len() as u64;
+        // A block larger than the whole budget can never be retained.
+        if len > self.max_bytes {
+            return;
+        }
+
+        self.tick += 1;
+        if let Some(previous) = self.blocks.insert(
+            key,
+            CachedBlock {
+                data,
+                last_used: self.tick,
+            },
+        ) {
+            self.used_bytes -= previous.data.len() as u64;
+        }
+        self.used_bytes += len;
+
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.used_bytes > self.max_bytes {
+            let victim = self
+                .blocks
+                .iter()
+                .min_by_key(|(_, block)| block.last_used)
+                .map(|(key, _)| key.clone());
+
+            match victim {
+                Some(key) => {
+                    if let Some(block) = self.blocks.remove(&key) {
+                        self.used_bytes -= block.data.len() as u64;
+                    }
+                }
+                None => break,
+            }
+        }
+    }
 }
 
 /// An interface to a cloud-storage.
@@ -39,6 +181,15 @@ pub trait Backend {
     async fn list_objects(&self, bucket_name: &str) -> eyre::Result<Vec<Object>>;
 
     async fn download_object(&self, bucket_name: &str, key: &str) -> eyre::Result<Bytes>;
+
+    /// Downloads the half-open byte range `[offset, offset + len)` of an object.
+    async fn download_range(
+        &self,
+        bucket_name: &str,
+        key: &str,
+        offset: u64,
+        len: u64,
+    ) -> eyre::Result<Bytes>;
 }
 
 #[derive(Debug)]
@@ -47,3 +198,56 @@ pub struct Object {
     pub size: u64,
     pub last_modified: SystemTime,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(index: u64, len: usize) -> (BlockKey, Bytes) {
+        (
+            ("bucket".into(), "key".into(), index),
+            Bytes::from(vec![0u8; len]),
+        )
+    }
+
+    #[test]
+    fn evicts_least_recently_used_when_over_budget() {
+        let mut cache = BlockCache::new(200);
+        let (k0, d0) = block(0, 100);
+        let (k1, d1) = block(1, 100);
+        let (k2, d2) = block(2, 100);
+
+        cache.insert(k0.clone(), d0);
+        cache.insert(k1.clone(), d1);
+        assert_eq!(cache.used_bytes, 200);
+
+        // Touching `k0` makes `k1` the least-recently-used, hence the next victim.
+        assert!(cache.get(&k0).is_some());
+        cache.insert(k2.clone(), d2);
+
+        assert_eq!(cache.used_bytes, 200);
+        assert!(cache.get(&k0).is_some());
+        assert!(cache.get(&k1).is_none());
+        assert!(cache.get(&k2).is_some());
+    }
+
+    #[test]
+    fn does_not_retain_blocks_larger_than_the_budget() {
+        let mut cache = BlockCache::new(50);
+        let (k, d) = block(0, 100);
+        cache.insert(k.clone(), d);
+
+        assert_eq!(cache.used_bytes, 0);
+        assert!(cache.get(&k).is_none());
+    }
+
+    #[test]
+    fn accounts_bytes_when_a_block_is_replaced() {
+        let mut cache = BlockCache::new(1000);
+        let (k, _) = block(0, 0);
+        cache.insert(k.clone(), Bytes::from(vec![0u8; 100]));
+        cache.insert(k.clone(), Bytes::from(vec![0u8; 40]));
+
+        assert_eq!(cache.used_bytes, 40);
+    }
+}
@@ -20,6 +20,8 @@ pub struct Config {
     pub filesystem: FilesystemOptions,
     #[serde(rename = "backend")]
     pub backend: BackendOptions,
+    #[serde(rename = "cache", default)]
+    pub cache: CacheOptions,
 }
 
 #[derive(Debug, Deserialize)]
@@ -34,11 +36,36 @@ pub struct FilesystemOptions {
     pub mountpoint: PathBuf,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct CacheOptions {
+    /// Upper bound on the bytes of object content retained across reads; `0` disables caching.
+    #[serde(rename = "max_bytes", default = "default_cache_max_bytes")]
+    pub max_bytes: u64,
+}
+
+impl Default for CacheOptions {
+    fn default() -> Self {
+        Self {
+            max_bytes: default_cache_max_bytes(),
+        }
+    }
+}
+
+fn default_cache_max_bytes() -> u64 {
+    128 * 1024 * 1024
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(tag = "provider")]
 pub enum BackendOptions {
     #[serde(rename = "aws")]
     Aws(backends::aws::Options),
+    #[serde(rename = "azure")]
+    Azure(backends::azure::Options),
+    #[serde(rename = "gcp")]
+    Gcp(backends::gcp::Options),
+    #[serde(rename = "local")]
+    Local(backends::local::Options),
 }
 
 impl Config {
@@ -55,7 +82,8 @@ impl Config {
 
 /// Starts the filesystem, mounting it at the specified location.
 pub fn run_app(cfg: Config, rt: Runtime) -> eyre::Result<()> {
-    let conn = new_connection_from(cfg.backend, rt);
+    let conn = new_connection_from(cfg.backend, rt, cfg.cache)
+        .wrap_err("unable to build backend connection")?;
 
     let fs = BucketFilesystem::new(cfg.source.bucket_name, conn)
         .wrap_err("unable to construct bucket fs")?;
@@ -75,10 +103,29 @@ fn start_fs(opts: FilesystemOptions, fs: BucketFilesystem) -> eyre::Result<()> {
     })
 }
 
-fn new_connection_from(opts: BackendOptions, rt: Runtime) -> BlockingConnection {
-    let backend = match opts {
-        BackendOptions::Aws(opts) => rt.block_on(backends::aws::AwsProvider::new(opts)),
+fn new_connection_from(
+    opts: BackendOptions,
+    rt: Runtime,
+    cache: CacheOptions,
+) -> eyre::Result<BlockingConnection> {
+    let conn = match opts {
+        BackendOptions::Aws(opts) => {
+            let backend = rt.block_on(backends::aws::Provider::new(opts));
+            BlockingConnection::new(backend, rt, cache.max_bytes)
+        }
+        BackendOptions::Azure(opts) => {
+            let backend = backends::azure::Provider::new(opts);
+            BlockingConnection::new(backend, rt, cache.max_bytes)
+        }
+        BackendOptions::Gcp(opts) => {
+            let backend = rt.block_on(backends::gcp::Provider::new(opts))?;
+            BlockingConnection::new(backend, rt, cache.max_bytes)
+        }
+        BackendOptions::Local(opts) => {
+            let backend = backends::local::Provider::new(opts);
+            BlockingConnection::new(backend, rt, cache.max_bytes)
+        }
     };
 
-    BlockingConnection::new(backend, rt)
+    Ok(conn)
 }